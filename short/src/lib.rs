@@ -2,20 +2,84 @@
 //!
 //! A small library which takes individual links and shortens them.
 
+#[cfg(feature = "server")]
+pub mod server;
+
 use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
 
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use sled::transaction::{ConflictableTransactionResult, TransactionError, Transactional};
 use thiserror::Error;
 use tokio::task::spawn_blocking;
 use tracing::{info, instrument, warn};
 use url::Url;
 use uuid::Uuid;
 
+/// The characters short codes are generated from.
+/// This is a URL-safe alphabet with visually ambiguous characters (`0`/`O`, `1`/`I`/`l`) removed.
+const CODE_ALPHABET: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz23456789";
+
+/// The length a generated short code starts at, mirroring nanoid's default.
+const DEFAULT_CODE_LENGTH: usize = 7;
+
+/// How many collisions we'll tolerate at a given code length before bumping it up by one.
+const COLLISION_RETRIES_PER_LENGTH: usize = 3;
+
+/// The hard ceiling on collisions we'll eat before giving up entirely.
+const MAX_COLLISION_ATTEMPTS: usize = 32;
+
+/// Generates a random short code of the given length from [`CODE_ALPHABET`].
+fn generate_code(length: usize) -> String {
+    let mut rng = rand::thread_rng();
+
+    (0..length)
+        .map(|_| {
+            let idx = rng.gen_range(0..CODE_ALPHABET.len());
+            CODE_ALPHABET[idx] as char
+        })
+        .collect()
+}
+
+/// Normalizes a [`Url`] so equivalent links hash to the same reverse-index key, e.g.
+/// `https://EXAMPLE.com:443/` and `https://example.com/` both become `https://example.com/`.
+fn normalize_url(url: &Url) -> String {
+    let mut normalized = url.clone();
+
+    if let Some(host) = url.host_str() {
+        let _ = normalized.set_host(Some(&host.to_lowercase()));
+    }
+
+    // `Url::port()` is only `Some` when the port was given explicitly (the crate already elides
+    // an explicit default port at parse time), so compare it against a portless copy's known
+    // default rather than the URL's own `port_or_known_default()` — which echoes back whatever
+    // explicit port is set and would make this always true.
+    if let Some(explicit_port) = url.port() {
+        let mut portless = url.clone();
+        let _ = portless.set_port(None);
+
+        if portless.port_or_known_default() == Some(explicit_port) {
+            let _ = normalized.set_port(None);
+        }
+    }
+
+    normalized.into()
+}
+
 /// The LinkManager is a way to easily integrate `direction` into your own projects!
 /// It will create a database of links, then add them to it for later recollection/editing.
 #[derive(Debug, Clone)]
 pub struct LinkManager {
     db: sled::Db,
+    /// Maps a normalized original URL to the short code already minted for it, so repeat
+    /// requests for the same URL reuse one entry instead of piling up duplicates.
+    url_index: sled::Tree,
+    /// Maps an alias to the primary short code it stands in for, so vanity names resolve
+    /// without duplicating the encoded `Link`.
+    aliases: sled::Tree,
+    /// Maps a link's `identifier` to its [`LinkStats`], updated on every resolution.
+    stats: sled::Tree,
 }
 
 impl LinkManager {
@@ -37,62 +101,349 @@ impl LinkManager {
 
         let location_clone = location.clone();
         let db: sled::Db = spawn_blocking(move || sled::open(location_clone)).await??;
+        let url_index = db.open_tree("url_index")?;
+        let aliases = db.open_tree("aliases")?;
+        let stats = db.open_tree("stats")?;
 
         info!(
             "A LinkManager has been created or accessed at the following location: {}",
             location.display()
         );
 
-        Ok(LinkManager { db })
+        Ok(LinkManager {
+            db,
+            url_index,
+            aliases,
+            stats,
+        })
+    }
+
+    /// Generates a short code that isn't already present in the `db`, growing the code length
+    /// if we keep colliding at the current one.
+    async fn generate_unique_code(&self) -> Result<String, LinkError> {
+        let mut length = DEFAULT_CODE_LENGTH;
+        let mut collisions_at_length = 0;
+
+        for _ in 0..MAX_COLLISION_ATTEMPTS {
+            let candidate = generate_code(length);
+
+            if self.db.get(&candidate)?.is_none() && self.aliases.get(&candidate)?.is_none() {
+                return Ok(candidate);
+            }
+
+            warn!("generated short code collided with an existing entry: {candidate}");
+            collisions_at_length += 1;
+
+            if collisions_at_length >= COLLISION_RETRIES_PER_LENGTH {
+                length += 1;
+                collisions_at_length = 0;
+            }
+        }
+
+        Err(LinkError::CollisionLimitExceeded)
     }
 
     /// Tries to generate a shorter link from a given link.
+    ///
+    /// If `original_link` has already been shortened, the existing [`Link`] is returned instead
+    /// of minting a new code - unless `force_new` is set, in which case a fresh, distinct code is
+    /// always generated (useful when the caller explicitly wants another alias for the same URL).
     #[instrument(skip(link))]
     pub async fn generate_link(
         &mut self,
         link: impl AsRef<str>,
         aliases: Option<Vec<String>>,
+        force_new: bool,
+        ttl: Option<Duration>,
     ) -> Result<Link, LinkError> {
         let link = link.as_ref(); // allow all kinds of strings :)
 
         let original_link = Url::parse(link)?;
+        let normalized = normalize_url(&original_link);
+        let expires_at = ttl.map(|ttl| SystemTime::now() + ttl);
+
+        if !force_new {
+            if let Some(existing) = self.url_index.get(&normalized)? {
+                let code = String::from_utf8_lossy(&existing).to_string();
+
+                match self.get_link_by_code(&code)? {
+                    Some(link) if !link.is_expired() => {
+                        return self.attach_aliases(link, aliases).await;
+                    }
+                    Some(expired) => self.remove_link_entries(&expired)?,
+                    None => {}
+                }
+            }
+        }
+
         let identifier = Uuid::new_v4();
 
         // deal with aliases
-        let aliases = aliases.map(|list| {
+        let aliases: Option<Vec<String>> = aliases.map(|list| {
             list.iter()
                 .map(|s| urlencoding::encode(s).to_string())
                 .collect()
         });
 
-        // TODO: actually make links shorten!
-        let shortened_link = "farts".into();
+        // make sure none of the requested aliases are already spoken for before we commit anything
+        if let Some(list) = &aliases {
+            for alias in list {
+                if self.db.get(alias)?.is_some() || self.aliases.get(alias)?.is_some() {
+                    return Err(LinkError::AliasTaken(alias.clone()));
+                }
+            }
+        }
+
+        let shortened_link = self.generate_unique_code().await?;
 
         let encapsulated_link = Link {
             identifier,
             original_link,
             shortened_link,
             aliases,
+            expires_at,
         };
 
         // add it to the db
-        // TODO: use aliases, too!
         self.db.insert(
             encapsulated_link.shortened_link.clone(),
             encapsulated_link.clone().encode().await?,
         )?;
 
+        // `force_new` mints an extra code for a URL that's already indexed; the reverse index
+        // must keep pointing non-forced callers at the original code, so only claim the slot
+        // when it's actually free.
+        if self.url_index.get(&normalized)?.is_none() {
+            self.url_index
+                .insert(normalized, encapsulated_link.shortened_link.as_bytes())?;
+        }
+
+        if let Some(list) = &encapsulated_link.aliases {
+            for alias in list {
+                self.aliases
+                    .insert(alias, encapsulated_link.shortened_link.as_bytes())?;
+            }
+        }
+
+        self.stats.insert(
+            encapsulated_link.identifier.as_bytes(),
+            LinkStats::fresh().encode()?,
+        )?;
+
         Ok(encapsulated_link)
     }
 
-    /// Attempts to find a link in the database - given its shortened form.
+    /// Registers any `requested` aliases (not yet URL-encoded) against an existing link found via
+    /// the URL-dedup path, so a caller attaching a new vanity name to an already-shortened URL
+    /// isn't silently ignored. Aliases that already point at this same link are a no-op; an alias
+    /// already taken by a *different* link is rejected with `LinkError::AliasTaken`.
+    async fn attach_aliases(
+        &mut self,
+        mut link: Link,
+        requested: Option<Vec<String>>,
+    ) -> Result<Link, LinkError> {
+        let Some(requested) = requested else {
+            return Ok(link);
+        };
+
+        let mut newly_registered = Vec::new();
+
+        for alias in requested {
+            let alias = urlencoding::encode(&alias).to_string();
+
+            match self.aliases.get(&alias)? {
+                Some(target) if target == link.shortened_link.as_bytes() => continue,
+                Some(_) => return Err(LinkError::AliasTaken(alias)),
+                None if self.db.get(&alias)?.is_some() => return Err(LinkError::AliasTaken(alias)),
+                None => newly_registered.push(alias),
+            }
+        }
+
+        if newly_registered.is_empty() {
+            return Ok(link);
+        }
+
+        for alias in &newly_registered {
+            self.aliases
+                .insert(alias.as_str(), link.shortened_link.as_bytes())?;
+        }
+
+        let mut aliases = link.aliases.clone().unwrap_or_default();
+        aliases.extend(newly_registered);
+        link.aliases = Some(aliases);
+
+        self.db
+            .insert(link.shortened_link.clone(), link.clone().encode().await?)?;
+
+        Ok(link)
+    }
+
+    /// Looks up a `Link` by its primary short code, without consulting aliases or touching stats.
+    fn get_link_by_code(&self, code: &str) -> Result<Option<Link>, LinkError> {
+        match self.db.get(code)? {
+            Some(ivec) => Ok(Some(bincode::deserialize(ivec.to_vec().as_slice())?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Looks up a `Link` by its short code or one of its aliases, without touching stats.
+    fn find_link(&self, code: &str) -> Result<Link, LinkError> {
+        if let Some(link) = self.get_link_by_code(code)? {
+            return Ok(link);
+        }
+
+        if let Some(primary) = self.aliases.get(code)? {
+            let primary = String::from_utf8_lossy(&primary).to_string();
+
+            if let Some(link) = self.get_link_by_code(&primary)? {
+                return Ok(link);
+            }
+        }
+
+        Err(LinkError::LinkNotFound(code.into()))
+    }
+
+    /// Attempts to find a link in the database - given its shortened form or one of its aliases.
+    /// Each successful resolution bumps that link's hit counter and `last_accessed` timestamp.
     pub async fn resolve_link(&self, short_link: impl AsRef<str>) -> Result<Link, LinkError> {
         let short_link = short_link.as_ref();
+        let link = self.find_link(short_link)?;
 
-        match self.db.get(short_link)? {
+        if link.is_expired() {
+            self.remove_link_entries(&link)?;
+            return Err(LinkError::LinkExpired(short_link.into()));
+        }
+
+        self.record_hit(&link.identifier)?;
+
+        Ok(link)
+    }
+
+    /// Removes a link's primary entry, reverse-index entry, alias entries, and stats entry in a
+    /// single sled transaction spanning all four trees, so a crash partway through can't leave
+    /// dangling aliases or a stale reverse-index entry behind.
+    fn remove_link_entries(&self, link: &Link) -> Result<(), LinkError> {
+        let normalized = normalize_url(&link.original_link);
+
+        let db: &sled::Tree = &self.db;
+
+        (db, &self.url_index, &self.aliases, &self.stats)
+            .transaction(
+                |(db, url_index, aliases, stats)| -> ConflictableTransactionResult<(), sled::Error> {
+                    db.remove(link.shortened_link.as_str())?;
+
+                    // a `force_new` duplicate shares its URL with the indexed link; leave that
+                    // link's reverse-index entry alone
+                    if url_index.get(normalized.as_str())?.as_deref()
+                        == Some(link.shortened_link.as_bytes())
+                    {
+                        url_index.remove(normalized.as_str())?;
+                    }
+                    stats.remove(link.identifier.as_bytes())?;
+
+                    if let Some(list) = &link.aliases {
+                        for alias in list {
+                            aliases.remove(alias.as_str())?;
+                        }
+                    }
+
+                    Ok(())
+                },
+            )
+            .map_err(|err| match err {
+                TransactionError::Abort(err) | TransactionError::Storage(err) => {
+                    LinkError::DbAccessFailure(err)
+                }
+            })
+    }
+
+    /// Scans the primary tree for expired links and removes them (and their aliases/reverse-index
+    /// entries), returning how many were reclaimed.
+    pub async fn purge_expired(&self) -> Result<usize, LinkError> {
+        let mut expired = Vec::new();
+
+        for entry in self.db.iter() {
+            let (_, ivec) = entry?;
+            let link: Link = bincode::deserialize(ivec.to_vec().as_slice())?;
+
+            if link.is_expired() {
+                expired.push(link);
+            }
+        }
+
+        let count = expired.len();
+        for link in &expired {
+            self.remove_link_entries(link)?;
+        }
+
+        Ok(count)
+    }
+
+    /// Atomically increments the hit counter and updates `last_accessed` for a link.
+    fn record_hit(&self, identifier: &Uuid) -> Result<(), LinkError> {
+        self.stats
+            .update_and_fetch(identifier.as_bytes(), |old| match old {
+                Some(bytes) => match bincode::deserialize::<LinkStats>(bytes) {
+                    Ok(mut stats) => {
+                        stats.hits += 1;
+                        stats.last_accessed = Some(SystemTime::now());
+                        stats.encode().ok()
+                    }
+                    Err(_) => {
+                        warn!("stats entry for {identifier} was corrupt; resetting it");
+                        LinkStats::fresh().encode().ok()
+                    }
+                },
+                None => {
+                    let mut stats = LinkStats::fresh();
+                    stats.hits = 1;
+                    stats.last_accessed = Some(SystemTime::now());
+                    stats.encode().ok()
+                }
+            })?;
+
+        Ok(())
+    }
+
+    /// Reads a link's usage statistics without resolving/redirecting it.
+    pub async fn stats(&self, code: impl AsRef<str>) -> Result<LinkStats, LinkError> {
+        let link = self.find_link(code.as_ref())?;
+
+        match self.stats.get(link.identifier.as_bytes())? {
             Some(ivec) => Ok(bincode::deserialize(ivec.to_vec().as_slice())?),
-            None => Err(LinkError::LinkNotFound(short_link.into())),
+            None => Ok(LinkStats::fresh()),
+        }
+    }
+
+    /// Removes a link's primary entry, aliases, reverse-index entry, and stats in one go.
+    /// Returns `LinkError::LinkNotFound` if `code` doesn't name a short code or alias.
+    pub async fn delete_link(&mut self, code: impl AsRef<str>) -> Result<(), LinkError> {
+        let link = self.find_link(code.as_ref())?;
+        self.remove_link_entries(&link)
+    }
+
+    /// Re-points an existing link at a new URL, re-validating it and fixing up the reverse index.
+    /// Returns `LinkError::LinkNotFound` if `code` doesn't name a short code or alias.
+    pub async fn update_target(
+        &mut self,
+        code: impl AsRef<str>,
+        new_url: impl AsRef<str>,
+    ) -> Result<Link, LinkError> {
+        let mut link = self.find_link(code.as_ref())?;
+        let new_target = Url::parse(new_url.as_ref())?;
+
+        let old_key = normalize_url(&link.original_link);
+        if self.url_index.get(&old_key)?.as_deref() == Some(link.shortened_link.as_bytes()) {
+            self.url_index.remove(old_key)?;
         }
+        self.url_index
+            .insert(normalize_url(&new_target), link.shortened_link.as_bytes())?;
+
+        link.original_link = new_target;
+        self.db
+            .insert(link.shortened_link.clone(), link.clone().encode().await?)?;
+
+        Ok(link)
     }
 }
 
@@ -103,9 +454,10 @@ impl LinkManager {
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Link {
     identifier: Uuid,
-    original_link: Url,
-    shortened_link: String,
+    pub(crate) original_link: Url,
+    pub(crate) shortened_link: String,
     aliases: Option<Vec<String>>,
+    expires_at: Option<SystemTime>,
 }
 
 impl Link {
@@ -114,6 +466,36 @@ impl Link {
     async fn encode(self) -> Result<Vec<u8>, LinkError> {
         Ok(bincode::serialize(&self)?)
     }
+
+    /// Whether this link's deadline, if any, has passed.
+    fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|deadline| deadline <= SystemTime::now())
+    }
+}
+
+/// Usage metrics for a single link, tracked alongside its encoded `Link`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LinkStats {
+    pub hits: u64,
+    pub created: SystemTime,
+    pub last_accessed: Option<SystemTime>,
+}
+
+impl LinkStats {
+    /// A freshly-minted set of stats for a link that hasn't been resolved yet.
+    fn fresh() -> Self {
+        Self {
+            hits: 0,
+            created: SystemTime::now(),
+            last_accessed: None,
+        }
+    }
+
+    /// Encodes the stats as a vector of bytes, mirroring [`Link::encode`].
+    fn encode(&self) -> Result<Vec<u8>, LinkError> {
+        Ok(bincode::serialize(self)?)
+    }
 }
 
 /// An error that occurs when handling links.
@@ -129,6 +511,12 @@ pub enum LinkError {
     LinkNotFound(String),
     #[error("internal error. blocking operation failed to join: {0}")]
     JoinError(#[from] tokio::task::JoinError),
+    #[error("exhausted retries while generating a unique short code")]
+    CollisionLimitExceeded,
+    #[error("alias already taken: {0}")]
+    AliasTaken(String),
+    #[error("link expired: {0}")]
+    LinkExpired(String),
 }
 
 #[cfg(test)]
@@ -139,24 +527,310 @@ mod tests {
     #[allow(unused)]
     use super::*;
 
+    /// Builds a `LinkManager` backed by its own throwaway directory, so tests don't trip over
+    /// each other by sharing the bare OS temp dir that `LinkManager::create(None)` falls back to.
+    async fn test_manager() -> LinkManager {
+        let dir = std::env::temp_dir().join(format!("short-test-{}", Uuid::new_v4()));
+        LinkManager::create(Some(dir)).await.unwrap()
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn try_generation() {
-        #![allow(unused_must_use)]
         tracing_subscriber::fmt::fmt()
             .with_max_level(Level::TRACE)
             .finish();
 
-        let mut lm = LinkManager::create(None).await.unwrap();
+        let mut lm = test_manager().await;
 
-        // Let's try to generate 20 links, then see what comes out!
         let our_link = String::from("https://farts.google.com");
 
-        lm.generate_link(our_link.clone(), None).await;
-        lm.generate_link(&our_link, None).await;
+        let first = lm
+            .generate_link(our_link.clone(), None, false, None)
+            .await
+            .unwrap();
+        let second = lm
+            .generate_link(&our_link, None, false, None)
+            .await
+            .unwrap();
+        assert_eq!(first.shortened_link, second.shortened_link);
 
         // how about Cow?
         let moooo = std::borrow::Cow::from("https://put.that.thang/away");
-        lm.generate_link(moooo, None).await;
+        let third = lm.generate_link(moooo, None, false, None).await.unwrap();
+        assert_ne!(third.shortened_link, first.shortened_link);
+    }
+
+    #[tokio::test]
+    async fn generate_unique_code_avoids_existing_codes_and_aliases() {
+        let lm = test_manager().await;
+
+        lm.db.insert("ABCDEFG", b"taken".as_ref()).unwrap();
+        lm.aliases.insert("HIJKLMN", b"ABCDEFG".as_ref()).unwrap();
+
+        for _ in 0..50 {
+            let code = lm.generate_unique_code().await.unwrap();
+            assert_ne!(code, "ABCDEFG");
+            assert_ne!(code, "HIJKLMN");
+        }
+    }
+
+    #[tokio::test]
+    async fn dedup_reuses_existing_code_for_equivalent_urls() {
+        let mut lm = test_manager().await;
+
+        let first = lm
+            .generate_link("https://example.com/page", None, false, None)
+            .await
+            .unwrap();
+        let second = lm
+            .generate_link("https://EXAMPLE.com:443/page", None, false, None)
+            .await
+            .unwrap();
+        assert_eq!(first.shortened_link, second.shortened_link);
+
+        let forced = lm
+            .generate_link("https://example.com/page", None, true, None)
+            .await
+            .unwrap();
+        assert_ne!(first.shortened_link, forced.shortened_link);
+    }
+
+    #[tokio::test]
+    async fn dedup_treats_distinct_explicit_ports_as_distinct_urls() {
+        let mut lm = test_manager().await;
+
+        let default_port = lm
+            .generate_link("https://example.com/page", None, false, None)
+            .await
+            .unwrap();
+        let custom_port_a = lm
+            .generate_link("https://example.com:8443/page", None, false, None)
+            .await
+            .unwrap();
+        let custom_port_b = lm
+            .generate_link("https://example.com:9999/page", None, false, None)
+            .await
+            .unwrap();
+
+        assert_ne!(default_port.shortened_link, custom_port_a.shortened_link);
+        assert_ne!(default_port.shortened_link, custom_port_b.shortened_link);
+        assert_ne!(custom_port_a.shortened_link, custom_port_b.shortened_link);
+
+        // re-requesting each still dedupes against itself
+        let repeat = lm
+            .generate_link("https://example.com:8443/page", None, false, None)
+            .await
+            .unwrap();
+        assert_eq!(repeat.shortened_link, custom_port_a.shortened_link);
+    }
+
+    #[tokio::test]
+    async fn force_new_does_not_repoint_the_dedup_index() {
+        let mut lm = test_manager().await;
+
+        let original = lm
+            .generate_link("https://example.com/page", None, false, None)
+            .await
+            .unwrap();
+        let forced = lm
+            .generate_link("https://example.com/page", None, true, None)
+            .await
+            .unwrap();
+        assert_ne!(original.shortened_link, forced.shortened_link);
+
+        // a later non-forced call must still resolve to the original code, not the forced one
+        let reused = lm
+            .generate_link("https://example.com/page", None, false, None)
+            .await
+            .unwrap();
+        assert_eq!(reused.shortened_link, original.shortened_link);
+    }
+
+    #[tokio::test]
+    async fn resolve_by_alias_and_reject_taken_alias() {
+        let mut lm = test_manager().await;
+
+        let link = lm
+            .generate_link(
+                "https://example.com/docs",
+                Some(vec!["docs".into()]),
+                false,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let resolved = lm.resolve_link("docs").await.unwrap();
+        assert_eq!(resolved.shortened_link, link.shortened_link);
+
+        let err = lm
+            .generate_link(
+                "https://example.com/other",
+                Some(vec!["docs".into()]),
+                false,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, LinkError::AliasTaken(_)));
+    }
+
+    #[tokio::test]
+    async fn dedup_attaches_new_aliases_to_the_existing_link() {
+        let mut lm = test_manager().await;
+
+        let first = lm
+            .generate_link("https://example.com/blog", None, false, None)
+            .await
+            .unwrap();
+        let reused = lm
+            .generate_link(
+                "https://example.com/blog",
+                Some(vec!["blog".into()]),
+                false,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(first.shortened_link, reused.shortened_link);
+
+        let via_alias = lm.resolve_link("blog").await.unwrap();
+        assert_eq!(via_alias.shortened_link, first.shortened_link);
+    }
+
+    #[tokio::test]
+    async fn stats_tracks_hits_and_last_accessed() {
+        let mut lm = test_manager().await;
+
+        let link = lm
+            .generate_link("https://example.com/hits", None, false, None)
+            .await
+            .unwrap();
+
+        let before = lm.stats(&link.shortened_link).await.unwrap();
+        assert_eq!(before.hits, 0);
+        assert!(before.last_accessed.is_none());
+
+        lm.resolve_link(&link.shortened_link).await.unwrap();
+        lm.resolve_link(&link.shortened_link).await.unwrap();
+
+        let after = lm.stats(&link.shortened_link).await.unwrap();
+        assert_eq!(after.hits, 2);
+        assert!(after.last_accessed.is_some());
+    }
+
+    #[tokio::test]
+    async fn purge_expired_reclaims_only_expired_links() {
+        let mut lm = test_manager().await;
+
+        let expiring = lm
+            .generate_link(
+                "https://example.com/a",
+                None,
+                false,
+                Some(Duration::from_millis(0)),
+            )
+            .await
+            .unwrap();
+        let keeper = lm
+            .generate_link("https://example.com/b", None, false, None)
+            .await
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let reclaimed = lm.purge_expired().await.unwrap();
+        assert_eq!(reclaimed, 1);
+
+        assert!(matches!(
+            lm.resolve_link(&expiring.shortened_link).await,
+            Err(LinkError::LinkNotFound(_))
+        ));
+        assert!(lm.resolve_link(&keeper.shortened_link).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn resolve_link_lazily_purges_an_expired_link() {
+        let mut lm = test_manager().await;
+
+        let link = lm
+            .generate_link(
+                "https://example.com/temp",
+                None,
+                false,
+                Some(Duration::from_millis(0)),
+            )
+            .await
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        let err = lm.resolve_link(&link.shortened_link).await.unwrap_err();
+        assert!(matches!(err, LinkError::LinkExpired(_)));
+
+        // the lazy purge already reclaimed it, so a second pass has nothing left to do
+        assert_eq!(lm.purge_expired().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn delete_link_removes_entry_and_aliases() {
+        let mut lm = test_manager().await;
+
+        let link = lm
+            .generate_link(
+                "https://example.com/del",
+                Some(vec!["del".into()]),
+                false,
+                None,
+            )
+            .await
+            .unwrap();
+
+        lm.delete_link(&link.shortened_link).await.unwrap();
+
+        assert!(matches!(
+            lm.resolve_link(&link.shortened_link).await,
+            Err(LinkError::LinkNotFound(_))
+        ));
+        assert!(matches!(
+            lm.resolve_link("del").await,
+            Err(LinkError::LinkNotFound(_))
+        ));
+        assert!(matches!(
+            lm.delete_link("missing").await,
+            Err(LinkError::LinkNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn update_target_repoints_link_and_reverse_index() {
+        let mut lm = test_manager().await;
+
+        let link = lm
+            .generate_link("https://example.com/old", None, false, None)
+            .await
+            .unwrap();
+
+        let updated = lm
+            .update_target(&link.shortened_link, "https://example.com/new")
+            .await
+            .unwrap();
+        assert_eq!(updated.original_link.as_str(), "https://example.com/new");
+
+        let resolved = lm.resolve_link(&link.shortened_link).await.unwrap();
+        assert_eq!(resolved.original_link.as_str(), "https://example.com/new");
+
+        // dedup should now find this link under its new URL
+        let reused = lm
+            .generate_link("https://example.com/new", None, false, None)
+            .await
+            .unwrap();
+        assert_eq!(reused.shortened_link, link.shortened_link);
+
+        assert!(matches!(
+            lm.update_target("missing", "https://example.com/z").await,
+            Err(LinkError::LinkNotFound(_))
+        ));
     }
 }
@@ -0,0 +1,178 @@
+//! An optional HTTP service that wraps [`LinkManager`] behind `POST /shorten` and `GET /{code}`
+//! endpoints, so `direction` can be run as a standalone shortener instead of only embedded as a
+//! library. Enabled via the `server` feature.
+
+use std::sync::Arc;
+
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+
+use crate::{LinkError, LinkManager};
+
+/// Shared state handed to every request handler.
+#[derive(Clone)]
+struct AppState {
+    manager: Arc<Mutex<LinkManager>>,
+}
+
+/// Builds the `axum` [`Router`] for the shortener service, ready to be served with
+/// `axum::serve` or nested into a larger application.
+pub fn router(manager: LinkManager) -> Router {
+    let state = AppState {
+        manager: Arc::new(Mutex::new(manager)),
+    };
+
+    Router::new()
+        .route("/shorten", post(shorten))
+        .route("/:code", get(redirect))
+        .with_state(state)
+}
+
+/// The request body for `POST /shorten`.
+#[derive(Debug, Deserialize)]
+struct ShortenRequest {
+    url: String,
+    aliases: Option<Vec<String>>,
+}
+
+/// The response body for `POST /shorten`.
+#[derive(Debug, Serialize, Deserialize)]
+struct ShortenResponse {
+    code: String,
+}
+
+async fn shorten(
+    State(state): State<AppState>,
+    Json(body): Json<ShortenRequest>,
+) -> Result<Json<ShortenResponse>, ApiError> {
+    let mut manager = state.manager.lock().await;
+    let link = manager
+        .generate_link(body.url, body.aliases, false, None)
+        .await?;
+
+    Ok(Json(ShortenResponse {
+        code: link.shortened_link,
+    }))
+}
+
+async fn redirect(
+    State(state): State<AppState>,
+    Path(code): Path<String>,
+) -> Result<Response, ApiError> {
+    let manager = state.manager.lock().await;
+    let link = manager.resolve_link(code).await?;
+
+    // `axum::response::Redirect` only offers 303/307/308; a shortener redirect is
+    // conventionally a 302 (or 301 for a permanent alias), so build the response by hand.
+    Ok((
+        StatusCode::FOUND,
+        [(header::LOCATION, link.original_link.to_string())],
+    )
+        .into_response())
+}
+
+/// Wraps a [`LinkError`] so it can be returned directly from `axum` handlers.
+struct ApiError(LinkError);
+
+impl From<LinkError> for ApiError {
+    fn from(err: LinkError) -> Self {
+        Self(err)
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = match self.0 {
+            LinkError::LinkNotFound(_) => StatusCode::NOT_FOUND,
+            LinkError::LinkExpired(_) => StatusCode::NOT_FOUND,
+            LinkError::InvalidLink(_) => StatusCode::BAD_REQUEST,
+            LinkError::AliasTaken(_) => StatusCode::CONFLICT,
+            LinkError::CollisionLimitExceeded => StatusCode::SERVICE_UNAVAILABLE,
+            LinkError::DbAccessFailure(_)
+            | LinkError::LinkEncodingFailure(_)
+            | LinkError::JoinError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        (status, self.0.to_string()).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use super::*;
+
+    async fn test_router() -> Router {
+        let dir = std::env::temp_dir().join(format!("short-server-test-{}", uuid::Uuid::new_v4()));
+        let manager = LinkManager::create(Some(dir)).await.unwrap();
+        router(manager)
+    }
+
+    async fn shorten(router: &Router, url: &str) -> ShortenResponse {
+        let response = router
+            .clone()
+            .oneshot(
+                Request::post("/shorten")
+                    .header("content-type", "application/json")
+                    .body(Body::from(
+                        serde_json::json!({ "url": url, "aliases": null }).to_string(),
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn shorten_then_redirect_round_trips_through_the_router() {
+        let router = test_router().await;
+        let shortened = shorten(&router, "https://example.com/page").await;
+
+        let response = router
+            .oneshot(
+                Request::get(format!("/{}", shortened.code))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FOUND);
+        assert_eq!(
+            response
+                .headers()
+                .get(header::LOCATION)
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "https://example.com/page"
+        );
+    }
+
+    #[tokio::test]
+    async fn redirect_on_unknown_code_returns_404() {
+        let router = test_router().await;
+
+        let response = router
+            .oneshot(Request::get("/does-not-exist").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}